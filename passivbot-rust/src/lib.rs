@@ -0,0 +1,33 @@
+use pyo3::prelude::*;
+
+mod constants;
+mod types;
+mod utils;
+
+#[pymodule]
+fn passivbot_rust(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(utils::round_up, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::round_, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::round_dn, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::round_up_decimal, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::round_decimal, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::round_dn_decimal, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::cost_to_qty, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::qty_to_cost, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::cost_to_qty_decimal, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::qty_to_cost_decimal, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_new_psize_pprice, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_new_psize_pprice_decimal, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_grid_levels, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::apply_spread, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_entry_price, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_close_price, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_wallet_exposure_checked, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_wallet_exposure_if_filled_checked, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_new_psize_pprice_checked, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_pnl_long_checked, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_pnl_short_checked, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::calc_auto_unstuck_allowance_checked, m)?)?;
+    Ok(())
+}
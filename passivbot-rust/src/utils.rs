@@ -1,6 +1,8 @@
-use crate::constants::{CLOSE, LONG, NO_POS, SHORT};
+use crate::constants::{CLOSE, ENTRY, LONG, NO_POS, SHORT};
 use crate::types::ExchangeParams;
 use pyo3::prelude::*;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::str::FromStr;
 
 /// Rounds a number to the specified number of decimal places.
 fn round_to_decimal_places(value: f64, decimal_places: usize) -> f64 {
@@ -29,6 +31,75 @@ pub fn round_dn(n: f64, step: f64) -> f64 {
     round_to_decimal_places(result, 12)
 }
 
+/// Converts an f64 to a `Decimal` via its shortest round-tripping string
+/// representation, so the conversion itself never introduces binary-float
+/// dust. Panics on non-finite input (`NaN`/`Infinity`), matching this file's
+/// existing convention of failing loudly on invalid input (see the `pside`
+/// checks below) rather than silently producing a wrong quantity of `0.0`.
+/// Magnitudes beyond `Decimal::MAX` (~7.9e28 — far past any real price, qty,
+/// or cost) saturate to `Decimal::MAX`/`Decimal::MIN` instead of panicking.
+fn f64_to_decimal(value: f64) -> Decimal {
+    assert!(value.is_finite(), "f64_to_decimal: non-finite input {}", value);
+    Decimal::from_str(&format!("{:e}", value))
+        .or_else(|_| Decimal::from_str(&value.to_string()))
+        .unwrap_or(if value.is_sign_negative() {
+            Decimal::MIN
+        } else {
+            Decimal::MAX
+        })
+}
+
+/// Converts a `Decimal` back to `f64`. Panics rather than silently returning
+/// `0.0` if the result is somehow non-finite.
+fn decimal_to_f64(value: Decimal) -> f64 {
+    let result: f64 = value
+        .to_string()
+        .parse()
+        .expect("Decimal's string representation always parses as f64");
+    assert!(result.is_finite(), "decimal_to_f64: {} is out of f64 range", value);
+    result
+}
+
+/// Exact counterpart to `round_up`: quantizes to the nearest multiple of `step`
+/// at or above `n`, computed entirely in base-10 fixed point so the result is
+/// an exact multiple of `step` with no floating-point residue.
+#[pyfunction]
+pub fn round_up_decimal(n: f64, step: f64) -> f64 {
+    let step = f64_to_decimal(step);
+    if step.is_zero() {
+        return n;
+    }
+    let n = f64_to_decimal(n);
+    let n_steps = (n / step).round_dp_with_strategy(0, RoundingStrategy::ToPositiveInfinity);
+    decimal_to_f64(n_steps * step)
+}
+
+/// Exact counterpart to `round_`: quantizes to the nearest multiple of `step`,
+/// ties rounding to even, computed entirely in base-10 fixed point.
+#[pyfunction]
+pub fn round_decimal(n: f64, step: f64) -> f64 {
+    let step = f64_to_decimal(step);
+    if step.is_zero() {
+        return n;
+    }
+    let n = f64_to_decimal(n);
+    let n_steps = (n / step).round_dp_with_strategy(0, RoundingStrategy::MidpointNearestEven);
+    decimal_to_f64(n_steps * step)
+}
+
+/// Exact counterpart to `round_dn`: quantizes to the nearest multiple of
+/// `step` at or below `n`, computed entirely in base-10 fixed point.
+#[pyfunction]
+pub fn round_dn_decimal(n: f64, step: f64) -> f64 {
+    let step = f64_to_decimal(step);
+    if step.is_zero() {
+        return n;
+    }
+    let n = f64_to_decimal(n);
+    let n_steps = (n / step).round_dp_with_strategy(0, RoundingStrategy::ToNegativeInfinity);
+    decimal_to_f64(n_steps * step)
+}
+
 #[pyfunction]
 pub fn calc_diff(x: f64, y: f64) -> f64 {
     if y == 0.0 {
@@ -56,6 +127,30 @@ pub fn qty_to_cost(qty: f64, price: f64, c_mult: f64) -> f64 {
     (qty.abs() * price) * c_mult
 }
 
+/// Exact counterpart to `cost_to_qty`, computed in base-10 fixed point so
+/// `qty_to_cost_decimal(cost_to_qty_decimal(c, p, m), p, m)` round-trips to
+/// `c` within one tick instead of drifting by binary-float dust.
+#[pyfunction]
+pub fn cost_to_qty_decimal(cost: f64, price: f64, c_mult: f64) -> f64 {
+    if price > 0.0 {
+        let cost = f64_to_decimal(cost);
+        let price = f64_to_decimal(price);
+        let c_mult = f64_to_decimal(c_mult);
+        decimal_to_f64((cost / price) / c_mult)
+    } else {
+        0.0
+    }
+}
+
+/// Exact counterpart to `qty_to_cost`, computed in base-10 fixed point.
+#[pyfunction]
+pub fn qty_to_cost_decimal(qty: f64, price: f64, c_mult: f64) -> f64 {
+    let qty = f64_to_decimal(qty).abs();
+    let price = f64_to_decimal(price);
+    let c_mult = f64_to_decimal(c_mult);
+    decimal_to_f64((qty * price) * c_mult)
+}
+
 pub fn calc_wallet_exposure(
     c_mult: f64,
     balance: f64,
@@ -107,6 +202,37 @@ pub fn calc_new_psize_pprice(
     )
 }
 
+/// Exact counterpart to `calc_new_psize_pprice`: the weighted-average price
+/// is computed in base-10 fixed point so the blended `pprice` carries no
+/// binary-float dust. The blended `pprice` is not rounded to any price step.
+#[pyfunction]
+pub fn calc_new_psize_pprice_decimal(
+    psize: f64,
+    pprice: f64,
+    qty: f64,
+    price: f64,
+    qty_step: f64,
+) -> (f64, f64) {
+    if qty == 0.0 {
+        return (psize, pprice);
+    }
+    if psize == 0.0 {
+        return (qty, price);
+    }
+    let new_psize = round_decimal(psize + qty, qty_step);
+    if new_psize == 0.0 {
+        return (0.0, 0.0);
+    }
+    let psize_d = f64_to_decimal(psize);
+    let pprice_d = f64_to_decimal(if pprice.is_nan() { 0.0 } else { pprice });
+    let qty_d = f64_to_decimal(qty);
+    let price_d = f64_to_decimal(price);
+    let new_psize_d = f64_to_decimal(new_psize);
+    let new_pprice =
+        pprice_d * (psize_d / new_psize_d) + price_d * (qty_d / new_psize_d);
+    (new_psize, decimal_to_f64(new_pprice))
+}
+
 fn nan_to_0(value: f64) -> f64 {
     if value.is_nan() {
         0.0
@@ -115,6 +241,66 @@ fn nan_to_0(value: f64) -> f64 {
     }
 }
 
+/// Builds geometrically spaced grid price levels anchored at `base_price`,
+/// the way a concentrated-liquidity AMM spaces its bins: `price_i = base_price
+/// * ratio^i` for longs and `base_price / ratio^i` for shorts. With `ratio < 1`
+/// long levels step down and short levels step up; with `ratio > 1` the
+/// directions are reversed.
+#[pyfunction]
+pub fn calc_grid_levels(base_price: f64, ratio: f64, n_levels: usize, pside: usize) -> Vec<f64> {
+    (0..n_levels)
+        .map(|i| match pside {
+            LONG => base_price * ratio.powi(i as i32),
+            SHORT => base_price / ratio.powi(i as i32),
+            _ => panic!("unknown pside {}", pside),
+        })
+        .collect()
+}
+
+/// Distributes `total_cost` across geometrically spaced grid levels so that
+/// liquidity `L = sqrt(qty_cost_i * price_i)` is equal in every bin (rather
+/// than notional being equal), yielding the triangular, martingale-style
+/// notional profile a grid bot wants. `prices` are expected to come from
+/// `calc_grid_levels`; quantities are rounded via `round_` and the last level
+/// absorbs the rounding remainder, so the summed cost matches `total_cost` to
+/// within half a `qty_step` of cost at the last price, rather than exactly,
+/// since the last level's qty is itself snapped to `qty_step` after the
+/// remainder is computed. If the earlier levels' rounded cost already exceeds
+/// `total_cost`, the last level gets qty `0.0` and the summed cost overshoots
+/// `total_cost` by that excess.
+pub fn calc_grid_qtys(
+    prices: &[f64],
+    total_cost: f64,
+    exchange_params: &ExchangeParams,
+) -> Vec<(f64, f64)> {
+    if prices.is_empty() || total_cost <= 0.0 {
+        return prices.iter().map(|&price| (price, 0.0)).collect();
+    }
+    let inv_sum: f64 = prices.iter().map(|&price| 1.0 / price).sum();
+    let l_squared = total_cost / inv_sum;
+    let mut qtys: Vec<f64> = prices
+        .iter()
+        .map(|&price| {
+            round_(
+                cost_to_qty(l_squared / price, price, exchange_params.c_mult),
+                exchange_params.qty_step,
+            )
+        })
+        .collect();
+    let last = qtys.len() - 1;
+    let allocated_cost: f64 = qtys[..last]
+        .iter()
+        .zip(&prices[..last])
+        .map(|(&qty, &price)| qty_to_cost(qty, price, exchange_params.c_mult))
+        .sum();
+    let remaining_cost = (total_cost - allocated_cost).max(0.0);
+    qtys[last] = round_(
+        cost_to_qty(remaining_cost, prices[last], exchange_params.c_mult),
+        exchange_params.qty_step,
+    );
+    prices.iter().copied().zip(qtys).collect()
+}
+
 pub fn interpolate(x: f64, xs: &[f64], ys: &[f64]) -> f64 {
     assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
 
@@ -134,6 +320,77 @@ pub fn interpolate(x: f64, xs: &[f64], ys: &[f64]) -> f64 {
     result
 }
 
+/// Piecewise cubic Hermite interpolation (PCHIP), a monotonicity-preserving
+/// replacement for `interpolate`: full Lagrange interpolation is O(n^2) per
+/// evaluation and oscillates badly (Runge phenomenon) over more than a
+/// handful of points, which is dangerous when feeding a parameter schedule or
+/// a volatility/allowance curve. `xs` must be strictly increasing; `x` is
+/// clamped to `[xs[0], xs[n-1]]` (flat extrapolation).
+pub fn interpolate_pchip(x: f64, xs: &[f64], ys: &[f64]) -> f64 {
+    assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
+    let n = xs.len();
+    if n == 0 {
+        // matches `interpolate`'s behavior for an empty slice
+        return 0.0;
+    }
+    if n == 1 {
+        return ys[0];
+    }
+
+    let x = x.clamp(xs[0], xs[n - 1]);
+
+    let h: Vec<f64> = (0..n - 1).map(|k| xs[k + 1] - xs[k]).collect();
+    let d: Vec<f64> = (0..n - 1).map(|k| (ys[k + 1] - ys[k]) / h[k]).collect();
+
+    let mut m = vec![0.0; n];
+    if n == 2 {
+        m[0] = d[0];
+        m[1] = d[0];
+    } else {
+        for k in 1..n - 1 {
+            let (d0, d1) = (d[k - 1], d[k]);
+            m[k] = if d0 == 0.0 || d1 == 0.0 || d0.signum() != d1.signum() {
+                0.0
+            } else {
+                let w1 = 2.0 * h[k] + h[k - 1];
+                let w2 = h[k] + 2.0 * h[k - 1];
+                (w1 + w2) / (w1 / d0 + w2 / d1)
+            };
+        }
+        m[0] = pchip_end_derivative(h[0], h[1], d[0], d[1]);
+        m[n - 1] = pchip_end_derivative(h[n - 2], h[n - 3], d[n - 2], d[n - 3]);
+    }
+
+    // locate the bracketing interval k such that xs[k] <= x <= xs[k + 1]
+    let k = match xs.partition_point(|&xk| xk <= x) {
+        0 => 0,
+        idx if idx >= n => n - 2,
+        idx => idx - 1,
+    };
+
+    let t = (x - xs[k]) / h[k];
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+    let h10 = t3 - 2.0 * t2 + t;
+    let h01 = -2.0 * t3 + 3.0 * t2;
+    let h11 = t3 - t2;
+
+    h00 * ys[k] + h10 * h[k] * m[k] + h01 * ys[k + 1] + h11 * h[k] * m[k + 1]
+}
+
+/// One-sided derivative estimate for a PCHIP endpoint, clamped to avoid
+/// overshoot past the adjacent secant slope.
+fn pchip_end_derivative(h0: f64, h1: f64, d0: f64, d1: f64) -> f64 {
+    let mut m = ((2.0 * h0 + h1) * d0 - h0 * d1) / (h0 + h1);
+    if m.signum() != d0.signum() {
+        m = 0.0;
+    } else if d0.signum() != d1.signum() && m.abs() > 3.0 * d0.abs() {
+        m = 3.0 * d0;
+    }
+    m
+}
+
 pub fn calc_pnl_long(entry_price: f64, close_price: f64, qty: f64, c_mult: f64) -> f64 {
     qty.abs() * c_mult * (close_price - entry_price)
 }
@@ -142,6 +399,40 @@ pub fn calc_pnl_short(entry_price: f64, close_price: f64, qty: f64, c_mult: f64)
     qty.abs() * c_mult * (entry_price - close_price)
 }
 
+/// Widens a reference price away from the market before it's rounded into an
+/// order, the way a market maker biases quotes off a ticker-derived mid with
+/// a single spread knob: long entries go to `ref * (1 - spread_pct)`, long
+/// closes to `ref * (1 + spread_pct)`, and the mirror for shorts. A
+/// `spread_pct` of `0.0` reproduces the raw reference price.
+#[pyfunction]
+pub fn apply_spread(price: f64, spread_pct: f64, pside: usize, order_type: usize) -> f64 {
+    match (pside, order_type) {
+        (LONG, ENTRY) => price * (1.0 - spread_pct),
+        (LONG, CLOSE) => price * (1.0 + spread_pct),
+        (SHORT, ENTRY) => price * (1.0 + spread_pct),
+        (SHORT, CLOSE) => price * (1.0 - spread_pct),
+        _ => panic!("unknown pside {} / order_type {}", pside, order_type),
+    }
+}
+
+/// Constructs an entry order price from a reference price: widens it via
+/// `apply_spread`, then snaps it onto the exchange's price grid. Both the
+/// backtester and the live engine should build entry prices through this
+/// function rather than rounding the raw reference price directly, so
+/// `spread_pct` behaves identically in both.
+#[pyfunction]
+pub fn calc_entry_price(ref_price: f64, spread_pct: f64, pside: usize, price_step: f64) -> f64 {
+    round_(apply_spread(ref_price, spread_pct, pside, ENTRY), price_step)
+}
+
+/// Constructs a close order price from a reference price: widens it via
+/// `apply_spread`, then snaps it onto the exchange's price grid. See
+/// `calc_entry_price`.
+#[pyfunction]
+pub fn calc_close_price(ref_price: f64, spread_pct: f64, pside: usize, price_step: f64) -> f64 {
+    round_(apply_spread(ref_price, spread_pct, pside, CLOSE), price_step)
+}
+
 pub fn calc_pprice_diff_int(pside: usize, pprice: f64, price: f64) -> f64 {
     match pside {
         LONG => {
@@ -175,4 +466,304 @@ pub fn calc_auto_unstuck_allowance(
     let balance_peak = balance + (pnl_cumsum_max - pnl_cumsum_last);
     let drop_since_peak_pct = balance / balance_peak - 1.0;
     (balance_peak * (loss_allowance_pct + drop_since_peak_pct)).max(0.0)
+}
+
+/// Error surfaced by the `*_checked` calculations instead of the silent
+/// `f64::INFINITY`/`NaN` the infallible versions can produce, which otherwise
+/// corrupts downstream wallet-exposure and PnL calculations without a trace.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MathError {
+    /// An input was `NaN` or infinite.
+    NonFiniteInput,
+    /// `balance` was negative.
+    NegativeBalance,
+    /// An intermediate result (e.g. division by a near-zero `new_psize`, or
+    /// an allowance blowing up) would have become non-finite.
+    Overflow,
+}
+
+impl std::fmt::Display for MathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MathError::NonFiniteInput => write!(f, "input is NaN or infinite"),
+            MathError::NegativeBalance => write!(f, "balance is negative"),
+            MathError::Overflow => write!(f, "computation would overflow to a non-finite value"),
+        }
+    }
+}
+
+impl std::error::Error for MathError {}
+
+impl From<MathError> for PyErr {
+    fn from(err: MathError) -> PyErr {
+        pyo3::exceptions::PyValueError::new_err(err.to_string())
+    }
+}
+
+fn require_finite(value: f64) -> Result<f64, MathError> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(MathError::NonFiniteInput)
+    }
+}
+
+fn require_finite_result(value: f64) -> Result<f64, MathError> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(MathError::Overflow)
+    }
+}
+
+/// Checked counterpart to `calc_wallet_exposure`: flags non-finite inputs and
+/// a negative `balance` instead of silently returning `0.0`.
+#[pyfunction]
+pub fn calc_wallet_exposure_checked(
+    c_mult: f64,
+    balance: f64,
+    position_size: f64,
+    position_price: f64,
+) -> PyResult<f64> {
+    require_finite(c_mult)?;
+    require_finite(balance)?;
+    require_finite(position_size)?;
+    require_finite(position_price)?;
+    if balance < 0.0 {
+        return Err(MathError::NegativeBalance.into());
+    }
+    if balance == 0.0 || position_size == 0.0 {
+        return Ok(0.0);
+    }
+    Ok(require_finite_result(
+        qty_to_cost(position_size, position_price, c_mult) / balance,
+    )?)
+}
+
+/// Checked counterpart to `calc_wallet_exposure_if_filled`.
+#[pyfunction]
+pub fn calc_wallet_exposure_if_filled_checked(
+    balance: f64,
+    psize: f64,
+    pprice: f64,
+    qty: f64,
+    price: f64,
+    exchange_params: &ExchangeParams,
+) -> PyResult<f64> {
+    require_finite(balance)?;
+    require_finite(psize)?;
+    require_finite(pprice)?;
+    require_finite(qty)?;
+    require_finite(price)?;
+    if balance < 0.0 {
+        return Err(MathError::NegativeBalance.into());
+    }
+    let psize = round_(psize.abs(), exchange_params.qty_step);
+    let qty = round_(qty.abs(), exchange_params.qty_step);
+    let (new_psize, new_pprice) =
+        calc_new_psize_pprice_checked(psize, pprice, qty, price, exchange_params.qty_step)?;
+    calc_wallet_exposure_checked(exchange_params.c_mult, balance, new_psize, new_pprice)
+}
+
+/// Checked counterpart to `calc_new_psize_pprice`: requires every input to be
+/// finite up front (rather than papering over a `NaN` `pprice` via
+/// `nan_to_0`) and flags a weighted average that blows up to a non-finite
+/// value instead of letting it propagate.
+#[pyfunction]
+pub fn calc_new_psize_pprice_checked(
+    psize: f64,
+    pprice: f64,
+    qty: f64,
+    price: f64,
+    qty_step: f64,
+) -> PyResult<(f64, f64)> {
+    require_finite(psize)?;
+    require_finite(pprice)?;
+    require_finite(qty)?;
+    require_finite(price)?;
+    if qty == 0.0 {
+        return Ok((psize, pprice));
+    }
+    if psize == 0.0 {
+        return Ok((qty, price));
+    }
+    let new_psize = round_(psize + qty, qty_step);
+    if new_psize == 0.0 {
+        return Ok((0.0, 0.0));
+    }
+    // `new_psize` is rounded to a multiple of `qty_step`, so any nonzero
+    // result already has magnitude >= `qty_step` — no separate near-zero
+    // guard is needed here; `require_finite_result` below catches the case
+    // where the weighted average itself blows up.
+    let new_pprice = pprice * (psize / new_psize) + price * (qty / new_psize);
+    Ok((new_psize, require_finite_result(new_pprice)?))
+}
+
+/// Checked counterpart to `calc_pnl_long`.
+#[pyfunction]
+pub fn calc_pnl_long_checked(
+    entry_price: f64,
+    close_price: f64,
+    qty: f64,
+    c_mult: f64,
+) -> PyResult<f64> {
+    require_finite(entry_price)?;
+    require_finite(close_price)?;
+    require_finite(qty)?;
+    require_finite(c_mult)?;
+    Ok(require_finite_result(
+        qty.abs() * c_mult * (close_price - entry_price),
+    )?)
+}
+
+/// Checked counterpart to `calc_pnl_short`.
+#[pyfunction]
+pub fn calc_pnl_short_checked(
+    entry_price: f64,
+    close_price: f64,
+    qty: f64,
+    c_mult: f64,
+) -> PyResult<f64> {
+    require_finite(entry_price)?;
+    require_finite(close_price)?;
+    require_finite(qty)?;
+    require_finite(c_mult)?;
+    Ok(require_finite_result(
+        qty.abs() * c_mult * (entry_price - close_price),
+    )?)
+}
+
+/// Checked counterpart to `calc_auto_unstuck_allowance`: flags a negative
+/// `balance` and a `balance_peak` of exactly `0.0`, which would otherwise
+/// divide out to a silent `Infinity`/`NaN`.
+#[pyfunction]
+pub fn calc_auto_unstuck_allowance_checked(
+    balance: f64,
+    loss_allowance_pct: f64,
+    pnl_cumsum_max: f64,
+    pnl_cumsum_last: f64,
+) -> PyResult<f64> {
+    require_finite(balance)?;
+    require_finite(loss_allowance_pct)?;
+    require_finite(pnl_cumsum_max)?;
+    require_finite(pnl_cumsum_last)?;
+    if balance < 0.0 {
+        return Err(MathError::NegativeBalance.into());
+    }
+    let balance_peak = balance + (pnl_cumsum_max - pnl_cumsum_last);
+    if balance_peak == 0.0 {
+        return Err(MathError::Overflow.into());
+    }
+    let drop_since_peak_pct = balance / balance_peak - 1.0;
+    Ok(require_finite_result(
+        (balance_peak * (loss_allowance_pct + drop_since_peak_pct)).max(0.0),
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_exact_multiple(value: f64, step: f64) -> bool {
+        let value = f64_to_decimal(value);
+        let step = f64_to_decimal(step);
+        value % step == Decimal::ZERO
+    }
+
+    #[test]
+    fn round_up_dn_decimal_bracket_input_and_are_exact_multiples() {
+        let cases = [
+            (1.000_000_000_01, 0.000_000_01),
+            (0.1 + 0.2, 0.01),
+            (123.456, 0.001),
+            (0.001_000_000_000_000_000_2, 0.001), // sub-tick float dust
+            (-7.5, 0.5),
+        ];
+        for (x, step) in cases {
+            let dn = round_dn_decimal(x, step);
+            let up = round_up_decimal(x, step);
+            assert!(dn <= x, "round_dn_decimal({}, {}) = {} should be <= {}", x, step, dn, x);
+            assert!(x <= up, "round_up_decimal({}, {}) = {} should be >= {}", x, step, up, x);
+            assert!(is_exact_multiple(dn, step), "round_dn_decimal({}, {}) = {} is not an exact multiple of step", x, step, dn);
+            assert!(is_exact_multiple(up, step), "round_up_decimal({}, {}) = {} is not an exact multiple of step", x, step, up);
+        }
+    }
+
+    #[test]
+    fn round_decimal_is_exact_multiple_and_ties_to_even() {
+        assert_eq!(round_decimal(0.125, 0.01), 0.12);
+        assert_eq!(round_decimal(0.135, 0.01), 0.14);
+        assert!(is_exact_multiple(round_decimal(0.001_000_000_000_000_000_2, 0.001), 0.001));
+    }
+
+    #[test]
+    fn cost_to_qty_decimal_round_trips_within_one_tick() {
+        let cases = [(100.0, 3.3333, 1.0), (0.0007, 62345.12, 1.0), (9999.99, 0.0001, 100.0)];
+        for (cost, price, c_mult) in cases {
+            let qty = cost_to_qty_decimal(cost, price, c_mult);
+            let round_tripped = qty_to_cost_decimal(qty, price, c_mult);
+            let tick = price * c_mult;
+            assert!(
+                (round_tripped - cost).abs() <= tick,
+                "round-trip of cost {} through cost_to_qty_decimal/qty_to_cost_decimal gave {}, off by more than one tick ({})",
+                cost,
+                round_tripped,
+                tick
+            );
+        }
+    }
+
+    #[test]
+    fn calc_new_psize_pprice_decimal_is_free_of_float_dust() {
+        let (new_psize, new_pprice) =
+            calc_new_psize_pprice_decimal(0.1, 100.0, 0.2, 100.0, 0.001);
+        assert!(is_exact_multiple(new_psize, 0.001));
+        assert_eq!(new_psize, 0.3);
+        assert_eq!(new_pprice, 100.0);
+    }
+
+    #[test]
+    fn interpolate_pchip_empty_slice_returns_zero() {
+        assert_eq!(interpolate_pchip(5.0, &[], &[]), 0.0);
+    }
+
+    #[test]
+    fn interpolate_pchip_flat_extrapolation_outside_range() {
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let ys = [0.0, 1.0, 4.0, 9.0];
+        assert_eq!(interpolate_pchip(-10.0, &xs, &ys), interpolate_pchip(0.0, &xs, &ys));
+        assert_eq!(interpolate_pchip(100.0, &xs, &ys), interpolate_pchip(3.0, &xs, &ys));
+    }
+
+    #[test]
+    fn interpolate_pchip_preserves_monotonicity() {
+        let xs = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let ys = [0.0, 1.0, 1.1, 5.0, 5.1];
+        let mut prev = interpolate_pchip(xs[0], &xs, &ys);
+        let mut x = xs[0];
+        while x <= xs[xs.len() - 1] {
+            let y = interpolate_pchip(x, &xs, &ys);
+            assert!(y + 1e-9 >= prev, "interpolant decreased at x = {}: {} < {}", x, y, prev);
+            prev = y;
+            x += 0.01;
+        }
+    }
+
+    #[test]
+    fn interpolate_pchip_does_not_overshoot_between_nodes() {
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let ys = [0.0, 10.0, 10.5, 0.0];
+        let (min_y, max_y) = (0.0_f64, 10.5_f64);
+        let mut x = xs[0];
+        while x <= xs[xs.len() - 1] {
+            let y = interpolate_pchip(x, &xs, &ys);
+            assert!(
+                y >= min_y - 1e-9 && y <= max_y + 1e-9,
+                "interpolant overshot data range at x = {}: y = {}",
+                x,
+                y
+            );
+            x += 0.01;
+        }
+    }
 }
\ No newline at end of file